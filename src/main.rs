@@ -22,15 +22,186 @@
 
 
 use std::env; // Для работы с окружением
+use std::fs::{File, OpenOptions}; // Для открытия файлов под редиректы
 use std::io::{self, Write, BufRead}; // Для ввода/вывода
 use std::process::{Command, Stdio}; // Для выполнения команд
 
+// Редиректы, разобранные из хвоста команды: >, >>, <, 2>
+#[derive(Default)]
+struct Redirection<'a> {
+    stdout: Option<&'a str>, // Куда пишем stdout (> или >>)
+    append: bool,            // true — >>, false — >
+    stdin: Option<&'a str>,  // Откуда читаем stdin (<)
+    stderr: Option<&'a str>, // Куда пишем stderr (2>)
+}
+
+// Вырезает токены редиректов из списка аргументов, возвращая оставшийся argv и саму структуру редиректов
+fn parse_redirections<'a>(args: &[&'a str]) -> (Vec<&'a str>, Redirection<'a>) {
+    let mut clean = Vec::new();
+    let mut redir = Redirection::default();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i] {
+            ">" => {
+                redir.stdout = args.get(i + 1).copied();
+                redir.append = false;
+                i += 2;
+            }
+            ">>" => {
+                redir.stdout = args.get(i + 1).copied();
+                redir.append = true;
+                i += 2;
+            }
+            "<" => {
+                redir.stdin = args.get(i + 1).copied();
+                i += 2;
+            }
+            "2>" => {
+                redir.stderr = args.get(i + 1).copied();
+                i += 2;
+            }
+            other => {
+                clean.push(other);
+                i += 1;
+            }
+        }
+    }
+    (clean, redir)
+}
+
+// Открывает файлы редиректов и подключает их к Command; ошибки открытия пробрасываем наружу
+fn apply_redirections(cmd: &mut Command, redir: &Redirection) -> io::Result<()> {
+    if let Some(path) = redir.stdin {
+        cmd.stdin(Stdio::from(File::open(path)?));
+    }
+    if let Some(path) = redir.stdout {
+        let file = if redir.append {
+            OpenOptions::new().create(true).append(true).open(path)?
+        } else {
+            File::create(path)?
+        };
+        cmd.stdout(Stdio::from(file));
+    }
+    if let Some(path) = redir.stderr {
+        cmd.stderr(Stdio::from(File::create(path)?));
+    }
+    Ok(())
+}
+
+// Открывает файлы > / >> / 2> для билтинов (которые печатают сами, а не через Command)
+// и передаёт их замыканию как stdout/stderr; без редиректов пишет прямо в реальные stdout/stderr.
+// Так `ps > out.txt`, `pwd`/`env`/`jobs` с редиректом и `2> err.log` у любого билтина работают
+// так же, как у внешних команд через apply_redirections.
+fn with_redirected_output(redir: &Redirection, f: impl FnOnce(&mut dyn Write, &mut dyn Write) -> i32) -> i32 {
+    let mut stdout_file = match redir.stdout {
+        Some(path) => {
+            let opened = if redir.append {
+                OpenOptions::new().create(true).append(true).open(path)
+            } else {
+                File::create(path)
+            };
+            match opened {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    return 1;
+                }
+            }
+        }
+        None => None,
+    };
+    let mut stderr_file = match redir.stderr {
+        Some(path) => match File::create(path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                return 1;
+            }
+        },
+        None => None,
+    };
+
+    let mut stdout_handle = io::stdout();
+    let mut stderr_handle = io::stderr();
+    let out: &mut dyn Write = stdout_file
+        .as_mut()
+        .map(|f| f as &mut dyn Write)
+        .unwrap_or(&mut stdout_handle);
+    let err: &mut dyn Write = stderr_file
+        .as_mut()
+        .map(|f| f as &mut dyn Write)
+        .unwrap_or(&mut stderr_handle);
+
+    f(out, err)
+}
+
+// Раскрывает $?, $NAME и ${NAME} в одном слове командной строки (вызывается уже после разбиения на аргументы)
+fn expand_variables(input: &str, last_status: i32) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut result = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '$' {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        if chars.get(i + 1) == Some(&'?') {
+            result.push_str(&last_status.to_string());
+            i += 2;
+        } else if chars.get(i + 1) == Some(&'{') {
+            if let Some(len) = chars[i + 2..].iter().position(|&c| c == '}') {
+                let name: String = chars[i + 2..i + 2 + len].iter().collect();
+                result.push_str(&env::var(&name).unwrap_or_default());
+                i += 2 + len + 1;
+            } else {
+                result.push(chars[i]); // Нет закрывающей `}` — оставляем как есть
+                i += 1;
+            }
+        } else if chars.get(i + 1).is_some_and(|c| c.is_alphabetic() || *c == '_') {
+            let mut j = i + 1;
+            while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
+                j += 1;
+            }
+            let name: String = chars[i + 1..j].iter().collect();
+            result.push_str(&env::var(&name).unwrap_or_default());
+            i = j;
+        } else {
+            result.push(chars[i]);
+            i += 1;
+        }
+    }
+    result
+}
+
+// Раскрывает ведущий `~` в аргументе cd в значение $HOME
+fn expand_tilde(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix('~') {
+        let home = env::var("HOME").unwrap_or_default();
+        format!("{}{}", home, rest)
+    } else {
+        path.to_string()
+    }
+}
+
+// Фоновая задача: её дочерние процессы и исходная командная строка, под номером job'а
+struct Job {
+    id: usize,
+    children: Vec<std::process::Child>,
+    command: String,
+}
+
 fn main() {
     let stdin = io::stdin(); // Получаем стандартный ввод
     let mut reader = stdin.lock(); // Блокируем ввод для чтения построчно
+    let mut jobs: Vec<Job> = Vec::new(); // Таблица фоновых задач
+    let mut next_job_id: usize = 1; // Счётчик номеров job'ов
+    let mut last_status: i32 = 0; // Код завершения последней выполненной команды ($?)
 
     loop {
-        print!("myshell> "); // Печатаем приглашение
+        reap_jobs(&mut jobs); // На каждом приглашении подчищаем завершившиеся фоновые задачи
+
+        print!("myshell[{}]> ", last_status); // Печатаем приглашение с последним кодом завершения
         io::stdout().flush().unwrap(); // Обеспечиваем вывод на экран
 
         let mut input = String::new(); // Строка для хранения ввода
@@ -43,68 +214,323 @@ fn main() {
             break; // Выход из оболочки
         }
 
+        let background = input.ends_with('&'); // `&` в конце строки — запуск в фоне
+        let input = if background {
+            input.trim_end_matches('&').trim()
+        } else {
+            input
+        };
+
         // Обработка конвейеров (pipeline)
         let commands: Vec<&str> = input.split('|').map(|s| s.trim()).collect(); // Разделяем команды
-        if commands.len() > 1 {
-            execute_pipeline(&commands); // Если есть несколько команд, выполняем конвейер
+        if background {
+            let children = spawn_pipeline(&commands, last_status, true); // Запускаем в фоне, не дожидаясь завершения
+            if let Some(first) = children.first() {
+                println!("[{}] {}", next_job_id, first.id()); // Как у настоящего шелла: номер job'а и pid
+                jobs.push(Job { id: next_job_id, children, command: input.to_string() });
+                next_job_id += 1;
+                last_status = 0; // Успешный fork — как у настоящего шелла, а не код предыдущей команды
+            }
+        } else if commands.len() > 1 {
+            last_status = execute_pipeline(&commands, last_status); // Если есть несколько команд, выполняем конвейер
         } else {
-            let args: Vec<&str> = input.split_whitespace().collect(); // Разделяем аргументы
-            match args[0] {
-                "cd" => { // Команда смены директории
-                    if let Err(e) = env::set_current_dir(args.get(1).unwrap_or(&"~").to_string()) {
-                        eprintln!("cd failed: {}", e); // Выводим ошибку, если не удалось сменить директорию
+            let raw_args: Vec<&str> = input.split_whitespace().collect(); // Разделяем аргументы
+            // $?/$NAME раскрываются уже после разбиения на слова, на стадии по `|` и проверки `&`,
+            // поэтому значение переменной не добавляет стадий конвейера и не уводит команду в фон.
+            // Редиректы разбираются после раскрытия: значение `>`, `>>`, `<` или `2>` работает как редирект
+            let expanded_args: Vec<String> = raw_args
+                .iter()
+                .map(|token| expand_variables(token, last_status))
+                .collect();
+            let expanded_refs: Vec<&str> = expanded_args.iter().map(String::as_str).collect();
+            let (args, redir) = parse_redirections(&expanded_refs); // Отделяем >, >>, <, 2> от настоящего argv
+            if args.is_empty() {
+                continue; // Пустая строка или строка из одних редиректов — нечего выполнять
+            }
+            last_status = match args[0] {
+                "cd" => with_redirected_output(&redir, |_out, err| { // Команда смены директории
+                    let target = expand_tilde(args.get(1).copied().unwrap_or("~"));
+                    match env::set_current_dir(target) {
+                        Ok(()) => 0,
+                        Err(e) => {
+                            let _ = writeln!(err, "cd failed: {}", e); // Выводим ошибку, если не удалось сменить директорию
+                            1
+                        }
                     }
-                }
-                "pwd" => { // Команда вывода текущей директории
-                    if let Ok(cwd) = env::current_dir() {
-                        println!("{}", cwd.display()); // Печатаем текущую директорию
+                }),
+                "export" => with_redirected_output(&redir, |_out, err| { // export NAME=value — задаёт переменную окружения
+                    match args.get(1).and_then(|kv| kv.split_once('=')) {
+                        Some((name, value)) => {
+                            env::set_var(name, value);
+                            0
+                        }
+                        None => {
+                            let _ = writeln!(err, "export: usage: export NAME=value");
+                            1
+                        }
                     }
-                }
-                "echo" => { // Команда вывода текста
-                    println!("{}", args[1..].join(" ")); // Печатаем все аргументы после echo
-                }
-                "kill" => { // Команда завершения процесса
-                    if let Ok(pid) = args[1].parse::<i32>() {
-                        let _ = Command::new("kill").arg(pid.to_string()).output(); // Убиваем процесс с указанным PID
+                }),
+                "unset" => with_redirected_output(&redir, |_out, err| { // unset NAME — удаляет переменную окружения
+                    match args.get(1) {
+                        Some(name) => {
+                            env::remove_var(name);
+                            0
+                        }
+                        None => {
+                            let _ = writeln!(err, "unset: usage: unset NAME");
+                            1
+                        }
+                    }
+                }),
+                "env" => with_redirected_output(&redir, |out, _err| { // Печатает все переменные окружения
+                    for (name, value) in env::vars() {
+                        let _ = writeln!(out, "{}={}", name, value);
+                    }
+                    0
+                }),
+                "pwd" => with_redirected_output(&redir, |out, _err| { // Команда вывода текущей директории
+                    match env::current_dir() {
+                        Ok(cwd) => {
+                            let _ = writeln!(out, "{}", cwd.display()); // Печатаем текущую директорию
+                            0
+                        }
+                        Err(_) => 1,
                     }
+                }),
+                "jobs" => with_redirected_output(&redir, |out, _err| { jobs_builtin(&jobs, out); 0 }), // Список фоновых задач
+                "fg" => with_redirected_output(&redir, |_out, err| fg_builtin(&mut jobs, &args, err)), // Дождаться job'а в форграунде
+                "wait" => with_redirected_output(&redir, |_out, err| wait_builtin(&mut jobs, &args, err)), // Дождаться одного job'а или всех
+                "echo" => { // Команда вывода текста
+                    let text = args[1..].join(" ");
+                    with_redirected_output(&redir, |out, err| match writeln!(out, "{}", text) {
+                        Ok(()) => 0,
+                        Err(e) => {
+                            let _ = writeln!(err, "echo: {}", e);
+                            1
+                        }
+                    })
                 }
-                "ps" => list_processes(), // Команда для вывода процессов
-                _ => execute_command(&args), // Выполняем любую другую команду
-            }
+                "kill" => with_redirected_output(&redir, |_out, err| kill_builtin(&args, err)), // Команда завершения процесса (поддерживает -SIGNAL и несколько PID)
+                "ps" => with_redirected_output(&redir, |out, _err| list_processes(out)), // Команда для вывода процессов
+                _ => execute_command(&args, &redir), // Выполняем любую другую команду
+            };
+        }
+    }
+}
+
+// Возвращает код завершения последней стадии конвейера
+fn execute_pipeline(commands: &[&str], last_status: i32) -> i32 {
+    let processes = spawn_pipeline(commands, last_status, false);
+    let mut last_code = -1;
+    // Ждем завершения только тех процессов, которые реально запустились
+    for mut process in processes {
+        if let Ok(status) = process.wait() {
+            last_code = status.code().unwrap_or(-1);
         }
     }
+    last_code
 }
 
-fn execute_pipeline(commands: &[&str]) {
+// Запускает все стадии конвейера и возвращает дочерние процессы, не дожидаясь их завершения.
+// `background` отключает наследование stdin у первой стадии, когда она не перехватывает вывод
+// предыдущей стадии и не имеет явного `<` — иначе фоновый job соревновался бы с интерактивным
+// циклом за ввод с терминала.
+fn spawn_pipeline(commands: &[&str], last_status: i32, background: bool) -> Vec<std::process::Child> {
     let mut processes: Vec<std::process::Child> = Vec::new(); // Храним дочерние процессы
+    let mut prev_stdout: Option<std::process::ChildStdout> = None; // stdout предыдущей стадии, если она была запущена
 
     for (i, command) in commands.iter().enumerate() {
-        let parts: Vec<&str> = command.split_whitespace().collect(); // Разделяем команду на части
+        let raw_parts: Vec<&str> = command.split_whitespace().collect(); // Разделяем команду на части
+        // Раскрываем переменные только после разбиения на слова — см. комментарий в main()
+        let expanded_parts: Vec<String> = raw_parts
+            .iter()
+            .map(|token| expand_variables(token, last_status))
+            .collect();
+        let expanded_refs: Vec<&str> = expanded_parts.iter().map(String::as_str).collect();
+        let (parts, redir) = parse_redirections(&expanded_refs); // Редиректы допустимы на любой стадии конвейера
+        if parts.is_empty() {
+            prev_stdout = None; // Пустая стадия (только редиректы) — нечего запускать, нечего читать дальше
+            continue;
+        }
 
-        // Запускаем команду с соответствующими параметрами
-        let mut child = Command::new(parts[0])
-            .args(&parts[1..])
-            .stdin(if i > 0 { Stdio::piped() } else { Stdio::inherit() }) // Если не первая команда, получаем ввод из предыдущей
-            .stdout(if i < commands.len() - 1 { Stdio::piped() } else { Stdio::inherit() }) // Если не последняя команда, выводим в следующую
-            .spawn()
-            .expect("Failed to start command"); // Запускаем команду
+        let mut cmd = Command::new(parts[0]);
+        cmd.args(&parts[1..]);
 
-        // Если не первая команда, перенаправляем stdout предыдущей команды в stdin текущей
-        if i > 0 {
-            let previous_stdout = processes[i - 1].stdout.take().expect("Failed to take stdout"); // Получаем stdout предыдущей команды
-            child.stdout = Some(previous_stdout); // Устанавливаем его как stdin текущей команды
+        // Если у предыдущей стадии есть stdout — читаем из него; иначе у фонового job'а
+        // отключаем stdin вовсе (явный `<` ниже всё равно его переопределит), а у
+        // интерактивного — наследуем stdin шелла
+        match prev_stdout.take() {
+            Some(stdout) => { cmd.stdin(Stdio::from(stdout)); }
+            None if background => { cmd.stdin(Stdio::null()); }
+            None => { cmd.stdin(Stdio::inherit()); }
+        }
+        // Все стадии, кроме последней, отдают вывод в следующую по конвейеру
+        if i < commands.len() - 1 {
+            cmd.stdout(Stdio::piped());
+        } else {
+            cmd.stdout(Stdio::inherit());
         }
 
-        processes.push(child); // Сохраняем дочерний процесс
+        // Явные редиректы на этой стадии перекрывают поведение по умолчанию
+        if let Err(e) = apply_redirections(&mut cmd, &redir) {
+            eprintln!("{}: {}", parts[0], e);
+            prev_stdout = None;
+            continue;
+        }
+
+        match cmd.spawn() {
+            Ok(mut child) => {
+                prev_stdout = child.stdout.take(); // Забираем stdout, чтобы передать его следующей стадии
+                processes.push(child); // Сохраняем дочерний процесс
+            }
+            Err(e) => {
+                eprintln!("{}: {}", parts[0], e); // Сообщаем об ошибке вместо паники
+                prev_stdout = None; // Следующей стадии нечего читать
+            }
+        }
     }
 
-    // Ждем завершения всех процессов
-    for mut process in processes {
-        let _ = process.wait().expect("Failed to wait for command"); // Ждем завершения
+    processes
+}
+
+// Убирает из таблицы job'ы, чьи процессы уже завершились, и сообщает об этом
+fn reap_jobs(jobs: &mut Vec<Job>) {
+    jobs.retain_mut(|job| {
+        let finished = job
+            .children
+            .iter_mut()
+            .all(|child| matches!(child.try_wait(), Ok(Some(_))));
+        if finished {
+            println!("[{}]+ Done\t{}", job.id, job.command);
+        }
+        !finished
+    });
+}
+
+// jobs — печатает таблицу фоновых задач в виде [n] <pid> <cmd>
+fn jobs_builtin(jobs: &[Job], out: &mut dyn Write) {
+    for job in jobs {
+        let pid = job.children[0].id();
+        let _ = writeln!(out, "[{}] {} {}", job.id, pid, job.command);
+    }
+}
+
+// Снимает job с указанным номером (или последний добавленный, если номер не передан)
+fn take_job(jobs: &mut Vec<Job>, args: &[&str]) -> Option<Job> {
+    let id = match args.get(1).and_then(|a| a.parse::<usize>().ok()) {
+        Some(id) => id,
+        None => jobs.last()?.id,
+    };
+    let pos = jobs.iter().position(|job| job.id == id)?;
+    Some(jobs.remove(pos))
+}
+
+// fg [n] — дожидается job'а в форграунде, возвращая код завершения последней стадии
+fn fg_builtin(jobs: &mut Vec<Job>, args: &[&str], err: &mut dyn Write) -> i32 {
+    match take_job(jobs, args) {
+        Some(mut job) => {
+            let mut code = -1;
+            for child in job.children.iter_mut() {
+                if let Ok(status) = child.wait() {
+                    code = status.code().unwrap_or(-1);
+                }
+            }
+            code
+        }
+        None => {
+            let _ = writeln!(err, "fg: no such job");
+            1
+        }
     }
 }
 
-fn execute_command(args: &[&str]) {
+// wait [n] — дожидается одного job'а, либо всех сразу, если номер не передан
+fn wait_builtin(jobs: &mut Vec<Job>, args: &[&str], err: &mut dyn Write) -> i32 {
+    if args.len() > 1 {
+        return fg_builtin(jobs, args, err); // Логика та же, что у fg: дождаться конкретный job
+    }
+    let mut code = 0;
+    for mut job in jobs.drain(..) {
+        for child in job.children.iter_mut() {
+            if let Ok(status) = child.wait() {
+                code = status.code().unwrap_or(-1);
+            }
+        }
+    }
+    code
+}
+
+// Переводит символическое или числовое имя сигнала (без ведущего дефиса) в номер сигнала
+fn parse_signal(spec: &str) -> i32 {
+    match spec.to_uppercase().as_str() {
+        "KILL" | "SIGKILL" => 9,
+        "TERM" | "SIGTERM" => 15,
+        "HUP" | "SIGHUP" => 1,
+        "INT" | "SIGINT" => 2,
+        other => other.parse().unwrap_or(15), // Неизвестное имя — считаем это числом либо шлём SIGTERM
+    }
+}
+
+// kill [-SIGNAL] <pid>... — без обращения к внешнему бинарю kill. Возвращает 0, если все PID обработаны успешно
+fn kill_builtin(args: &[&str], err: &mut dyn Write) -> i32 {
+    if args.len() < 2 {
+        let _ = writeln!(err, "kill: usage: kill [-SIGNAL] <pid>...");
+        return 1;
+    }
+
+    let (signal, pids) = if let Some(spec) = args[1].strip_prefix('-') {
+        (parse_signal(spec), &args[2..])
+    } else {
+        (15, &args[1..]) // По умолчанию — SIGTERM, как у настоящего kill
+    };
+
+    if pids.is_empty() {
+        let _ = writeln!(err, "kill: usage: kill [-SIGNAL] <pid>...");
+        return 1;
+    }
+
+    let mut ok = true;
+    for pid_str in pids {
+        match pid_str.parse::<i32>() {
+            Ok(pid) => {
+                if let Err(e) = send_signal(pid, signal) {
+                    let _ = writeln!(err, "kill: ({}) - {}", pid, e); // Репортим каждый PID отдельно, а не молчим
+                    ok = false;
+                }
+            }
+            Err(_) => {
+                let _ = writeln!(err, "kill: {}: arguments must be process or job IDs", pid_str);
+                ok = false;
+            }
+        }
+    }
+    if ok { 0 } else { 1 }
+}
+
+#[cfg(unix)]
+fn send_signal(pid: i32, signal: i32) -> Result<(), String> {
+    let ret = unsafe { libc::kill(pid, signal) }; // Шлём сигнал напрямую через syscall
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error().to_string())
+    }
+}
+
+#[cfg(windows)]
+fn send_signal(pid: i32, _signal: i32) -> Result<(), String> {
+    // На Windows нет POSIX-сигналов — SIGKILL/SIGTERM сводятся к принудительному завершению по PID
+    let status = Command::new("taskkill")
+        .args(&["/PID", &pid.to_string(), "/F"])
+        .status();
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(s) => Err(format!("taskkill exited with {}", s)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn execute_command(args: &[&str], redir: &Redirection) -> i32 {
     match args[0] {
         "dir" => {
             // Выполняем dir с помощью командной строки
@@ -114,37 +540,161 @@ fn execute_command(args: &[&str]) {
                 .expect("Failed to execute dir command");
             // Выводим результат
             println!("{}", String::from_utf8_lossy(&output.stdout)); // Печатаем вывод
+            output.status.code().unwrap_or(-1)
         }
         _ => {
-            let status = Command::new(args[0])
-                .args(&args[1..]) // Запускаем любую другую команду
-                .status()
-                .expect("Command failed to execute");
+            let mut cmd = Command::new(args[0]);
+            cmd.args(&args[1..]); // Запускаем любую другую команду
+
+            if let Err(e) = apply_redirections(&mut cmd, redir) {
+                eprintln!("{}: {}", args[0], e);
+                return -1;
+            }
+
+            let status = match cmd.status() {
+                Ok(status) => status,
+                Err(e) => {
+                    eprintln!("{}: {}", args[0], e); // Сообщаем об ошибке вместо паники
+                    return -1;
+                }
+            };
 
             if !status.success() {
                 eprintln!("Command exited with status: {}", status); // Выводим статус завершения команды
             }
+            status.code().unwrap_or(-1) // Так и задуман ExitStatus::code — используем его напрямую
         }
     }
 }
 
-fn list_processes() {
-    #[cfg(target_os = "linux")]
+fn list_processes(out: &mut dyn Write) -> i32 {
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
     {
-        // Код для Linux (не реализован)
-    }
-
-    #[cfg(target_os = "windows")]
-    {
-        use sysinfo::{System}; // Импортируем библиотеку для получения информации о системе
+        use sysinfo::System; // Импортируем библиотеку для получения информации о системе
 
         let mut system = System::new(); // Создаем новый объект системы
         system.refresh_all(); // Обновляем информацию о процессах
 
-        for (pid, process) in system.processes() { // Проходим по всем процессам
+        // id процесса, название, время работы в мсек — сортируем по PID для стабильного вывода
+        let mut processes: Vec<_> = system.processes().iter().collect();
+        processes.sort_by_key(|(pid, _)| **pid);
+
+        let _ = writeln!(out, "{:>8} {:<24} {:>12}", "PID", "NAME", "RUNTIME_MS");
+        for (pid, process) in processes {
             let name = process.name().to_string_lossy(); // Преобразование имени процесса в строку
-            let cpu_usage = process.cpu_usage(); // Получаем использование CPU
-            println!("{} {} {:.2}%", pid, name, cpu_usage); // Печатаем PID, имя и использование CPU
+            let runtime_ms = process.run_time() * 1000; // run_time() отдаёт секунды, переводим в мсек
+            let _ = writeln!(out, "{:>8} {:<24} {:>12}", pid, name, runtime_ms);
         }
     }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_redirections_extracts_all_operators() {
+        let tokens = ["cmd", "arg", ">", "out.txt", "<", "in.txt", "2>", "err.txt"];
+        let (argv, redir) = parse_redirections(&tokens);
+        assert_eq!(argv, vec!["cmd", "arg"]);
+        assert_eq!(redir.stdout, Some("out.txt"));
+        assert!(!redir.append);
+        assert_eq!(redir.stdin, Some("in.txt"));
+        assert_eq!(redir.stderr, Some("err.txt"));
+    }
+
+    #[test]
+    fn parse_redirections_append_sets_flag() {
+        let tokens = ["cmd", ">>", "out.txt"];
+        let (argv, redir) = parse_redirections(&tokens);
+        assert_eq!(argv, vec!["cmd"]);
+        assert_eq!(redir.stdout, Some("out.txt"));
+        assert!(redir.append);
+    }
+
+    #[test]
+    fn parse_redirections_with_only_operators_leaves_empty_argv() {
+        let tokens = [">", "out.txt"];
+        let (argv, _redir) = parse_redirections(&tokens);
+        assert!(argv.is_empty());
+    }
+
+    #[test]
+    fn expand_variables_replaces_last_status() {
+        assert_eq!(expand_variables("exit=$?", 7), "exit=7");
+    }
+
+    #[test]
+    fn expand_variables_replaces_dollar_name_and_braces() {
+        env::set_var("WBL28_TEST_VAR", "value");
+        assert_eq!(expand_variables("$WBL28_TEST_VAR", 0), "value");
+        assert_eq!(expand_variables("${WBL28_TEST_VAR}", 0), "value");
+        env::remove_var("WBL28_TEST_VAR");
+    }
+
+    #[test]
+    fn expand_variables_unset_name_becomes_empty() {
+        env::remove_var("WBL28_TEST_UNSET");
+        assert_eq!(expand_variables("[$WBL28_TEST_UNSET]", 0), "[]");
+    }
+
+    #[test]
+    fn expand_variables_unterminated_brace_is_left_as_is() {
+        assert_eq!(expand_variables("${WBL28_TEST_VAR", 0), "${WBL28_TEST_VAR");
+    }
+
+    #[test]
+    fn expand_tilde_expands_leading_tilde() {
+        env::set_var("HOME", "/home/test");
+        assert_eq!(expand_tilde("~"), "/home/test");
+        assert_eq!(expand_tilde("~/notes"), "/home/test/notes");
+        assert_eq!(expand_tilde("/abs/path"), "/abs/path");
+    }
+
+    #[test]
+    fn parse_signal_accepts_numeric_symbolic_and_full_posix_names() {
+        assert_eq!(parse_signal("9"), 9);
+        assert_eq!(parse_signal("KILL"), 9);
+        assert_eq!(parse_signal("SIGKILL"), 9);
+        assert_eq!(parse_signal("term"), 15);
+        assert_eq!(parse_signal("SIGTERM"), 15);
+        assert_eq!(parse_signal("HUP"), 1);
+        assert_eq!(parse_signal("INT"), 2);
+    }
+
+    #[test]
+    fn parse_signal_falls_back_to_sigterm_for_unknown_name() {
+        assert_eq!(parse_signal("NOTASIGNAL"), 15);
+    }
+
+    fn spawn_noop_job(id: usize) -> Job {
+        let child = Command::new("true")
+            .spawn()
+            .expect("failed to spawn `true` for test setup");
+        Job { id, children: vec![child], command: "true".to_string() }
+    }
+
+    #[test]
+    fn take_job_by_explicit_number() {
+        let mut jobs = vec![spawn_noop_job(1), spawn_noop_job(2)];
+        let taken = take_job(&mut jobs, &["fg", "1"]).expect("job 1 should exist");
+        assert_eq!(taken.id, 1);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, 2);
+    }
+
+    #[test]
+    fn take_job_defaults_to_most_recent() {
+        let mut jobs = vec![spawn_noop_job(1), spawn_noop_job(2)];
+        let taken = take_job(&mut jobs, &["fg"]).expect("a job should exist");
+        assert_eq!(taken.id, 2);
+        assert_eq!(jobs.len(), 1);
+    }
+
+    #[test]
+    fn take_job_missing_number_returns_none() {
+        let mut jobs = vec![spawn_noop_job(1)];
+        assert!(take_job(&mut jobs, &["fg", "42"]).is_none());
+    }
 }